@@ -0,0 +1,567 @@
+use std::{
+    alloc::{Layout, alloc, dealloc},
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull, addr_of_mut, drop_in_place},
+};
+
+/// The highest `strong` or `weak` count `Shared<T>`/`Weak<T>` will tolerate
+/// before aborting the process, mirroring the standard library's `Arc`
+/// hardening against `mem::forget`-induced overflow.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+/// Increments `*count`, aborting the process rather than letting it wrap if
+/// it would exceed [`MAX_REFCOUNT`]. Every `strong`/`weak` increment site
+/// goes through this so none of them can reintroduce the overflow.
+fn checked_increment(count: &mut usize) {
+    *count += 1;
+
+    if *count > MAX_REFCOUNT {
+        std::process::abort();
+    }
+}
+
+// `value` must stay the last field: when `T` is unsized, Rust requires the
+// dynamically-sized field to be declared last, and `Shared::from_box` relies
+// on the sized prefix (`strong`, `weak`) having the same layout regardless
+// of `T`.
+struct Inner<T: ?Sized> {
+    strong: usize,
+    weak: usize,
+    value: T,
+}
+
+pub struct Shared<T: ?Sized> {
+    ptr: NonNull<Inner<T>>,
+}
+
+pub struct Weak<T: ?Sized> {
+    ptr: NonNull<Inner<T>>,
+}
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self::try_new(value).unwrap_or_else(|_| panic!("[Shared] Allocation failed."))
+    }
+}
+
+impl<T> Shared<T> {
+    /// Like [`Shared::new`], but returns the value back instead of panicking
+    /// if the allocation fails.
+    pub fn try_new(value: T) -> Result<Self, T> {
+        let inner = Inner {
+            strong: 1,
+            weak: 1,
+            value,
+        };
+
+        let layout = Layout::for_value(&inner);
+
+        let raw_ptr = unsafe { alloc(layout) } as *mut Inner<T>;
+        if raw_ptr.is_null() {
+            return Err(inner.value);
+        }
+
+        unsafe {
+            raw_ptr.write(inner);
+        }
+
+        Ok(Self {
+            ptr: unsafe { NonNull::new_unchecked(raw_ptr) },
+        })
+    }
+}
+
+impl<T: ?Sized> Shared<T> {
+    /// Builds a `Shared<T>` from an owned, possibly unsized value, copying
+    /// its bytes into a fresh `Inner<T>` allocation sized from `value`'s
+    /// layout. This is the only constructor available when `T` is a slice
+    /// or trait object, since `new` can't take an unsized argument by value.
+    pub fn from_box(value: Box<T>) -> Self {
+        let box_ptr = Box::into_raw(value);
+        let value_layout = Layout::for_value(unsafe { &*box_ptr });
+
+        let (inner_layout, _offset) = Layout::new::<Inner<()>>()
+            .extend(value_layout)
+            .expect("[Shared] Invalid layout for value.");
+        let inner_layout = inner_layout.pad_to_align();
+
+        let raw_ptr = unsafe { alloc(inner_layout) };
+        if raw_ptr.is_null() {
+            panic!("[Shared] Allocation failed.")
+        }
+
+        let inner_ptr = unsafe { set_data_ptr(box_ptr as *mut Inner<T>, raw_ptr) };
+
+        unsafe {
+            addr_of_mut!((*inner_ptr).strong).write(1);
+            addr_of_mut!((*inner_ptr).weak).write(1);
+            ptr::copy_nonoverlapping(
+                box_ptr as *const u8,
+                addr_of_mut!((*inner_ptr).value) as *mut u8,
+                value_layout.size(),
+            );
+
+            // `Box` never actually allocates for a zero-sized value, so
+            // there's nothing to free back in that case.
+            if value_layout.size() != 0 {
+                dealloc(box_ptr as *mut u8, value_layout);
+            }
+        }
+
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(inner_ptr) },
+        }
+    }
+}
+
+impl<T: ?Sized> Shared<T> {
+    pub fn borrow(&self) -> &T {
+        let inner = unsafe { self.ptr.as_ref() };
+        &inner.value
+    }
+
+    pub fn borrow_mut(&mut self) -> &mut T {
+        let inner = unsafe { self.ptr.as_mut() };
+        &mut inner.value
+    }
+}
+
+impl<T: ?Sized> Shared<T> {
+    /// Creates a non-owning `Weak<T>` pointing at the same allocation.
+    pub fn downgrade(&self) -> Weak<T> {
+        let inner = unsafe { &mut *self.ptr.as_ptr() };
+        checked_increment(&mut inner.weak);
+
+        Weak { ptr: self.ptr }
+    }
+}
+
+impl<T: ?Sized> Shared<T> {
+    /// Returns the number of `Shared<T>` pointers to this allocation.
+    pub fn strong_count(&self) -> usize {
+        let inner = unsafe { self.ptr.as_ref() };
+        inner.strong
+    }
+
+    /// Returns `true` if the two `Shared<T>`s point to the same allocation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        ptr::addr_eq(self.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Returns a unique mutable reference to the value, cloning it into a
+    /// fresh allocation first if other `Shared<T>` owners exist, so the
+    /// caller's mutation is isolated from them. A clone is also made when an
+    /// outstanding `Weak<T>` could later `upgrade` and observe the mutation,
+    /// not just when another `Shared<T>` is currently alive.
+    pub fn make_mut(&mut self) -> &mut T {
+        let inner = unsafe { self.ptr.as_ref() };
+
+        // `weak == 1` is just the implicit weak reference held collectively
+        // by the strong pointers (see `Shared`'s drop logic), so this is
+        // unique only when no real `Weak<T>` exists either.
+        if inner.strong != 1 || inner.weak != 1 {
+            let cloned = Shared::new(self.borrow().clone());
+            *self = cloned;
+        }
+
+        self.borrow_mut()
+    }
+}
+
+impl<T> Shared<T> {
+    /// Reclaims sole ownership of the value if `this` is the only `Shared<T>`
+    /// pointing at the allocation, succeeding even if `Weak<T>`s are still
+    /// outstanding (they'll simply fail to upgrade afterwards).
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        if this.strong_count() != 1 {
+            return Err(this);
+        }
+
+        let raw_ptr = this.ptr.as_ptr();
+        let value = unsafe { ptr::read(&(*raw_ptr).value) };
+
+        // `this` no longer owns a valid `value`; skip its `Drop` impl and
+        // finish releasing the strong/weak count ourselves.
+        mem::forget(this);
+
+        let inner = unsafe { &mut *raw_ptr };
+        inner.strong -= 1;
+        inner.weak -= 1;
+        if inner.weak == 0 {
+            let layout = Layout::for_value(inner);
+            unsafe {
+                dealloc(raw_ptr as *mut u8, layout);
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+impl<T: ?Sized> Weak<T> {
+    /// Attempts to upgrade the weak pointer to a `Shared<T>`, returning
+    /// `None` if the value has already been dropped.
+    pub fn upgrade(&self) -> Option<Shared<T>> {
+        let inner = unsafe { &mut *self.ptr.as_ptr() };
+        if inner.strong == 0 {
+            return None;
+        }
+
+        checked_increment(&mut inner.strong);
+        Some(Shared { ptr: self.ptr })
+    }
+}
+
+impl<T: ?Sized> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.borrow()
+    }
+}
+
+impl<T: ?Sized> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        let raw_ptr = self.ptr.as_ptr();
+
+        let inner = unsafe { &mut *raw_ptr };
+        checked_increment(&mut inner.strong);
+
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T: ?Sized> Clone for Weak<T> {
+    fn clone(&self) -> Self {
+        let raw_ptr = self.ptr.as_ptr();
+
+        let inner = unsafe { &mut *raw_ptr };
+        checked_increment(&mut inner.weak);
+
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T: ?Sized> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let raw_ptr = self.ptr.as_ptr();
+
+        let inner = unsafe { &mut *raw_ptr };
+        inner.strong -= 1;
+
+        if inner.strong == 0 {
+            unsafe {
+                drop_in_place(&mut inner.value);
+            }
+
+            // Release the implicit weak reference held collectively by the
+            // strong pointers, then free the allocation if no `Weak<T>` is
+            // still keeping it alive.
+            inner.weak -= 1;
+            if inner.weak == 0 {
+                let layout = Layout::for_value(inner);
+                unsafe {
+                    dealloc(raw_ptr as *mut u8, layout);
+                }
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for Weak<T> {
+    fn drop(&mut self) {
+        let raw_ptr = self.ptr.as_ptr();
+
+        let inner = unsafe { &mut *raw_ptr };
+        inner.weak -= 1;
+
+        if inner.weak == 0 && inner.strong == 0 {
+            let layout = Layout::for_value(inner);
+            unsafe {
+                dealloc(raw_ptr as *mut u8, layout);
+            }
+        }
+    }
+}
+
+/// Overwrites the data pointer of a fat pointer while keeping its metadata
+/// (length or vtable), relying on the data address being the first word of
+/// `*mut T`, whether that pointer is thin or fat.
+unsafe fn set_data_ptr<T: ?Sized, U>(mut ptr: *mut T, data: *mut U) -> *mut T {
+    unsafe {
+        ptr::write(&mut ptr as *mut _ as *mut *mut u8, data as *mut u8);
+    }
+    ptr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shared;
+
+    #[test]
+    fn read_and_write_numbers() {
+        let mut x = Shared::new(10);
+        assert_eq!(*x, 10);
+
+        *x.borrow_mut() += 5;
+        assert_eq!(*x, 15);
+    }
+
+    #[test]
+    fn works_with_strings() {
+        let mut s = Shared::new("Shared".to_string());
+        assert_eq!(*s, "Shared");
+
+        s.borrow_mut().push_str(" pointer!");
+        assert_eq!(*s, "Shared pointer!");
+    }
+
+    #[test]
+    fn works_with_structs() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut p = Shared::new(Point { x: 1, y: 2 });
+        assert_eq!(*p, Point { x: 1, y: 2 });
+
+        let p = p.borrow_mut();
+        p.x = 10;
+        p.y = 20;
+        assert_eq!(*p, Point { x: 10, y: 20 });
+    }
+
+    #[test]
+    fn works_with_vectors() {
+        let mut v = Shared::new(vec![1, 2, 3]);
+        assert_eq!(*v, vec![1, 2, 3]);
+
+        v.borrow_mut().push(4);
+        assert_eq!(*v, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn multiple_clones() {
+        let a = Shared::new(100);
+        let mut b = a.clone();
+        let c = a.clone();
+
+        assert_eq!(*a, 100);
+        assert_eq!(*b, 100);
+        assert_eq!(*c, 100);
+
+        *b.borrow_mut() += 50;
+        assert_eq!(*a, 150);
+        assert_eq!(*c, 150);
+    }
+
+    #[test]
+    fn drop_frees_memory() {
+        struct FreeMemory<'a> {
+            flag: &'a mut bool,
+        }
+
+        impl<'a> Drop for FreeMemory<'a> {
+            fn drop(&mut self) {
+                *self.flag = true;
+            }
+        }
+
+        let mut released = false;
+
+        {
+            let a = Shared::new(FreeMemory {
+                flag: &mut released,
+            });
+            let _b = a.clone();
+        }
+
+        assert!(released);
+    }
+
+    #[test]
+    fn clone_reaches_max_refcount_without_aborting() {
+        let a = Shared::new(0);
+
+        // Poke `strong` right up against the boundary instead of actually
+        // leaking `MAX_REFCOUNT` clones, which would take forever.
+        unsafe {
+            (*a.ptr.as_ptr()).strong = super::MAX_REFCOUNT - 1;
+        }
+
+        let b = a.clone();
+        assert_eq!(a.strong_count(), super::MAX_REFCOUNT);
+
+        std::mem::forget(b);
+    }
+
+    #[test]
+    fn downgrade_and_upgrade() {
+        let a = Shared::new(42);
+        let weak = a.downgrade();
+
+        let upgraded = weak.upgrade().expect("value should still be alive");
+        assert_eq!(*upgraded, 42);
+    }
+
+    #[test]
+    fn upgrade_fails_after_drop() {
+        let a = Shared::new(42);
+        let weak = a.downgrade();
+
+        drop(a);
+
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn weak_keeps_allocation_alive_without_value() {
+        struct FreeMemory<'a> {
+            flag: &'a mut bool,
+        }
+
+        impl<'a> Drop for FreeMemory<'a> {
+            fn drop(&mut self) {
+                *self.flag = true;
+            }
+        }
+
+        let mut released = false;
+        let weak = {
+            let a = Shared::new(FreeMemory {
+                flag: &mut released,
+            });
+            a.downgrade()
+        };
+
+        assert!(weak.upgrade().is_none());
+        drop(weak);
+
+        assert!(released);
+    }
+
+    #[test]
+    fn from_box_supports_slices() {
+        let boxed: Box<[i32]> = vec![1, 2, 3, 4].into_boxed_slice();
+        let shared: Shared<[i32]> = Shared::from_box(boxed);
+
+        assert_eq!(&*shared, &[1, 2, 3, 4]);
+
+        let cloned = shared.clone();
+        assert_eq!(&*cloned, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_box_supports_trait_objects() {
+        trait Greet {
+            fn greet(&self) -> String;
+        }
+
+        struct Named(String);
+
+        impl Greet for Named {
+            fn greet(&self) -> String {
+                format!("hello {}", self.0)
+            }
+        }
+
+        let boxed: Box<dyn Greet> = Box::new(Named("world".to_string()));
+        let shared: Shared<dyn Greet> = Shared::from_box(boxed);
+
+        assert_eq!(shared.greet(), "hello world");
+    }
+
+    #[test]
+    fn make_mut_clones_on_write_when_shared() {
+        let mut a = Shared::new(vec![1, 2, 3]);
+        let b = a.clone();
+
+        a.make_mut().push(4);
+
+        assert_eq!(*a, vec![1, 2, 3, 4]);
+        assert_eq!(*b, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn make_mut_clones_on_write_when_weak_outstanding() {
+        let mut a = Shared::new(vec![1, 2, 3]);
+        let weak = a.downgrade();
+
+        a.make_mut().push(4);
+
+        assert_eq!(*a, vec![1, 2, 3, 4]);
+
+        // `a` now points at a fresh allocation; the old one `weak` was
+        // watching lost its only strong owner, so it can no longer upgrade
+        // to observe the mutation.
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn make_mut_mutates_in_place_when_unique() {
+        let mut a = Shared::new(vec![1, 2, 3]);
+        let ptr_before = a.borrow() as *const Vec<i32>;
+
+        a.make_mut().push(4);
+
+        assert_eq!(*a, vec![1, 2, 3, 4]);
+        assert_eq!(a.borrow() as *const Vec<i32>, ptr_before);
+    }
+
+    #[test]
+    fn strong_count_and_ptr_eq() {
+        let a = Shared::new(10);
+        assert_eq!(a.strong_count(), 1);
+
+        let b = a.clone();
+        assert_eq!(a.strong_count(), 2);
+        assert!(a.ptr_eq(&b));
+
+        let c = Shared::new(10);
+        assert!(!a.ptr_eq(&c));
+    }
+
+    #[test]
+    fn try_new_succeeds() {
+        let a = Shared::try_new(42).expect("allocation should succeed");
+        assert_eq!(*a, 42);
+    }
+
+    #[test]
+    fn try_unwrap_returns_value_when_unique() {
+        let a = Shared::new("unwrap me".to_string());
+        let value = Shared::try_unwrap(a).unwrap_or_else(|_| panic!("unique strong owner"));
+        assert_eq!(value, "unwrap me");
+    }
+
+    #[test]
+    fn try_unwrap_fails_when_shared() {
+        let a = Shared::new(42);
+        let b = a.clone();
+
+        let a = Shared::try_unwrap(a).unwrap_err();
+        assert_eq!(*a, 42);
+        assert_eq!(*b, 42);
+    }
+
+    #[test]
+    fn try_unwrap_succeeds_with_outstanding_weak() {
+        let a = Shared::new(42);
+        let weak = a.downgrade();
+
+        let value = Shared::try_unwrap(a).unwrap_or_else(|_| panic!("unique strong owner"));
+        assert_eq!(value, 42);
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    alloc::{Layout, alloc, dealloc},
+    ops::Deref,
+    ptr::{NonNull, drop_in_place},
+    sync::atomic::{AtomicUsize, Ordering, fence},
+};
+
+/// The highest `ref_count` `SharedSync<T>` will tolerate before aborting the
+/// process, mirroring the standard library's `Arc` hardening against
+/// `mem::forget`-induced overflow.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+struct Inner<T> {
+    value: T,
+    ref_count: AtomicUsize,
+}
+
+/// An `Arc`-style sibling of [`crate::Shared`] whose reference count is an
+/// `AtomicUsize`, making it safe to send clones across threads.
+pub struct SharedSync<T> {
+    ptr: NonNull<Inner<T>>,
+}
+
+unsafe impl<T: Send + Sync> Send for SharedSync<T> {}
+unsafe impl<T: Send + Sync> Sync for SharedSync<T> {}
+
+impl<T> SharedSync<T> {
+    pub fn new(value: T) -> Self {
+        let inner = Inner {
+            value,
+            ref_count: AtomicUsize::new(1),
+        };
+
+        let layout = Layout::for_value(&inner);
+
+        let raw_ptr = unsafe { alloc(layout) } as *mut Inner<T>;
+        if raw_ptr.is_null() {
+            panic!("[SharedSync] Allocation failed.")
+        }
+
+        unsafe {
+            raw_ptr.write(inner);
+        }
+
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(raw_ptr) },
+        }
+    }
+}
+
+impl<T> SharedSync<T> {
+    pub fn borrow(&self) -> &T {
+        let inner = unsafe { self.ptr.as_ref() };
+        &inner.value
+    }
+
+    /// Returns a unique mutable reference to the value if this is the only
+    /// `SharedSync<T>` pointing at the allocation, `None` otherwise. Unlike
+    /// `Shared::borrow_mut`, this can't simply hand out `&mut T` through a
+    /// shallow clone: other clones may be aliased on another thread right
+    /// now, so mutation is only safe once the count provably says "one".
+    /// The check is an `Acquire` load so that, if we're the thread that just
+    /// watched the count drop to one, every write the other owner made
+    /// before releasing is visible before we touch `value`.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        let count = unsafe { self.ptr.as_ref() }.ref_count.load(Ordering::Acquire);
+        if count != 1 {
+            return None;
+        }
+
+        let inner = unsafe { self.ptr.as_mut() };
+        Some(&mut inner.value)
+    }
+}
+
+impl<T> Deref for SharedSync<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.borrow()
+    }
+}
+
+impl<T> Clone for SharedSync<T> {
+    fn clone(&self) -> Self {
+        let inner = unsafe { self.ptr.as_ref() };
+
+        // The existing reference already establishes happens-before, so a
+        // relaxed increment is sufficient here.
+        let old_count = inner.ref_count.fetch_add(1, Ordering::Relaxed);
+
+        if old_count >= MAX_REFCOUNT {
+            std::process::abort();
+        }
+
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for SharedSync<T> {
+    fn drop(&mut self) {
+        let raw_ptr = self.ptr.as_ptr();
+        let inner = unsafe { &*raw_ptr };
+
+        if inner.ref_count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+
+        // Synchronize with every other thread's release decrement so that
+        // all prior writes are visible before we destroy the value.
+        fence(Ordering::Acquire);
+
+        let layout = Layout::for_value(inner);
+        unsafe {
+            drop_in_place(raw_ptr);
+            dealloc(raw_ptr as *mut u8, layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedSync;
+    use std::{sync::atomic::Ordering, thread};
+
+    #[test]
+    fn read_and_write_numbers() {
+        let mut x = SharedSync::new(10);
+        assert_eq!(*x, 10);
+
+        *x.get_mut().unwrap() += 5;
+        assert_eq!(*x, 15);
+    }
+
+    #[test]
+    fn works_with_strings() {
+        let mut s = SharedSync::new("SharedSync".to_string());
+        assert_eq!(*s, "SharedSync");
+
+        s.get_mut().unwrap().push_str(" pointer!");
+        assert_eq!(*s, "SharedSync pointer!");
+    }
+
+    #[test]
+    fn multiple_clones_share_reads() {
+        let a = SharedSync::new(100);
+        let b = a.clone();
+        let c = a.clone();
+
+        assert_eq!(*a, 100);
+        assert_eq!(*b, 100);
+        assert_eq!(*c, 100);
+    }
+
+    #[test]
+    fn get_mut_requires_unique_ownership() {
+        let mut a = SharedSync::new(100);
+        let b = a.clone();
+
+        assert!(a.get_mut().is_none());
+
+        drop(b);
+        *a.get_mut().expect("sole owner after drop") += 50;
+        assert_eq!(*a, 150);
+    }
+
+    #[test]
+    fn clone_reaches_max_refcount_without_aborting() {
+        let a = SharedSync::new(0);
+
+        // Poke `ref_count` right up against the boundary instead of actually
+        // leaking `MAX_REFCOUNT` clones, which would take forever.
+        unsafe {
+            (*a.ptr.as_ptr())
+                .ref_count
+                .store(super::MAX_REFCOUNT - 1, Ordering::Relaxed);
+        }
+
+        let b = a.clone();
+        assert_eq!(
+            unsafe { (*a.ptr.as_ptr()).ref_count.load(Ordering::Relaxed) },
+            super::MAX_REFCOUNT
+        );
+
+        std::mem::forget(b);
+    }
+
+    #[test]
+    fn drop_frees_memory() {
+        struct FreeMemory<'a> {
+            flag: &'a mut bool,
+        }
+
+        impl<'a> Drop for FreeMemory<'a> {
+            fn drop(&mut self) {
+                *self.flag = true;
+            }
+        }
+
+        let mut released = false;
+
+        {
+            let a = SharedSync::new(FreeMemory {
+                flag: &mut released,
+            });
+            let _b = a.clone();
+        }
+
+        assert!(released);
+    }
+
+    #[test]
+    fn clones_can_be_sent_across_threads() {
+        let a = SharedSync::new(42);
+        let b = a.clone();
+
+        let handle = thread::spawn(move || {
+            assert_eq!(*b, 42);
+        });
+
+        handle.join().unwrap();
+        assert_eq!(*a, 42);
+    }
+}